@@ -22,9 +22,15 @@
 // SOFTWARE.
 // ---------------------------------------------------------------------
 
+use std::collections::HashMap;
+
 use rand::Rng;
 use rand::seq::SliceRandom;
 
+/// Sentinel character prepended to every training word so the Markov
+/// chain has a well-defined prefix to start generation from.
+const SENTINEL: char = '\u{0}';
+
 /// An alphabet is a collection of symbols which will be used to
 /// create a (pseudo)-random sequence (aka password).
 pub struct Alphabet {
@@ -52,16 +58,89 @@ impl Alphabet {
     pub fn nth(&self, n: usize) -> char {
         self.symbols[n]
     }
+
+    /// Returns the preset alphabet of lowercase letters `a`-`z`.
+    pub fn lowercase() -> Self {
+        Alphabet::new("abcdefghijklmnopqrstuvwxyz")
+    }
+
+    /// Returns the preset alphabet of uppercase letters `A`-`Z`.
+    pub fn uppercase() -> Self {
+        Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ")
+    }
+
+    /// Returns the preset alphabet of digits `0`-`9`.
+    pub fn digits() -> Self {
+        Alphabet::new("0123456789")
+    }
+
+    /// Returns the preset alphabet of common symbol characters.
+    pub fn symbols() -> Self {
+        Alphabet::new("!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~")
+    }
+
+    /// Returns a preset alphabet of lowercase letters and digits with
+    /// visually ambiguous glyphs removed (e.g. `0`/`O`, `1`/`l`/`I`),
+    /// so that generated passwords are safe to transcribe by hand.
+    pub fn non_confusable() -> Self {
+        Alphabet::new("34678abcdefhjkmnpqrtuwxy")
+    }
+}
+
+/// Resolves a preset alphabet by its CLI shorthand name, e.g. `lower`,
+/// `upper`, `digits`, `symbols` or `nonconf`. Returns `None` if `name`
+/// does not match a known preset.
+pub fn preset_alphabet(name: &str) -> Option<Alphabet> {
+    match name {
+        "lower" | "lowercase" => Some(Alphabet::lowercase()),
+        "upper" | "uppercase" => Some(Alphabet::uppercase()),
+        "digits" | "digit" => Some(Alphabet::digits()),
+        "symbols" | "symbol" => Some(Alphabet::symbols()),
+        "nonconf" | "non_confusable" => Some(Alphabet::non_confusable()),
+        _ => None,
+    }
 }
 
+/// Errors that can occur while producing a password or passphrase,
+/// returned instead of panicking so `pwdgen::core` is safe to embed in
+/// larger applications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PwdError {
+    /// The requested output size (password length or passphrase word
+    /// count) was zero.
+    ZeroLength,
+    /// The requested length was smaller than the number of alphabets,
+    /// which need at least one symbol each to be represented.
+    LengthTooShort,
+    /// No symbol source (alphabets or wordlist) was given to draw from.
+    NoAlphabets,
+}
+
+impl std::fmt::Display for PwdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let message = match self {
+            PwdError::ZeroLength => "length must be greater than zero",
+            PwdError::LengthTooShort => "length must be greater or equal the number of alphabets",
+            PwdError::NoAlphabets => "at least one alphabet must be given",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for PwdError {}
+
 /// Produces a single password from multiple alphabets.
-pub fn produce_password(length: usize, alphabets: &Vec<Alphabet>) -> String {
-    if length < alphabets.len() {
-        panic!("Password-length must be greater or equal the number of alphabets.");
+pub fn produce_password(length: usize, alphabets: &Vec<Alphabet>) -> Result<String, PwdError> {
+    if length == 0 {
+        return Err(PwdError::ZeroLength);
     }
 
-    if alphabets.len() < 1 {
-        panic!("At least one alphabet must be given to create a password!")
+    if alphabets.is_empty() {
+        return Err(PwdError::NoAlphabets);
+    }
+
+    if length < alphabets.len() {
+        return Err(PwdError::LengthTooShort);
     }
 
     let mut rng = rand::thread_rng();
@@ -90,5 +169,197 @@ pub fn produce_password(length: usize, alphabets: &Vec<Alphabet>) -> String {
 
     // Shuffle the password to randomly distribute the initial symbols:
     password.shuffle(&mut rng);
-    password.iter().collect() // Type inference magic
+    Ok(password.iter().collect()) // Type inference magic
+}
+
+/// Produces a Diceware-style passphrase by drawing `words` entries
+/// uniformly at random from `wordlist` and joining them with `separator`.
+///
+/// Returns [`PwdError::ZeroLength`] if `words` is zero and
+/// [`PwdError::NoAlphabets`] if `wordlist` is empty.
+pub fn produce_passphrase(words: usize, separator: &str, wordlist: &[&str]) -> Result<String, PwdError> {
+    if words == 0 {
+        return Err(PwdError::ZeroLength);
+    }
+
+    if wordlist.is_empty() {
+        return Err(PwdError::NoAlphabets);
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut chosen = Vec::new();
+
+    for _ in 0..words {
+        let i = rng.gen_range(0..wordlist.len());
+        chosen.push(wordlist[i]);
+    }
+
+    Ok(chosen.join(separator))
+}
+
+/// A character-level Markov chain trained on a corpus of words, mapping
+/// each `order`-character prefix to a frequency table of the characters
+/// that followed it.
+///
+/// Training walks the whole corpus, so callers that need to generate
+/// many passwords (e.g. the CLI's bulk `-n` mode) should train a chain
+/// once with [`MarkovChain::train`] and reuse it across calls to
+/// [`produce_pronounceable`], rather than retraining per password.
+pub struct MarkovChain {
+    table: HashMap<String, HashMap<char, usize>>,
+    order: usize,
+}
+
+impl MarkovChain {
+    /// Trains a chain of the given `order` on [`crate::corpus::CORPUS`].
+    /// Each word is padded with `order` leading sentinels so the first
+    /// real characters are reachable from a known starting prefix.
+    pub fn train(order: usize) -> Self {
+        let mut table: HashMap<String, HashMap<char, usize>> = HashMap::new();
+
+        for word in crate::corpus::CORPUS {
+            let chars: Vec<char> = std::iter::repeat_n(SENTINEL, order)
+                .chain(word.chars())
+                .collect();
+
+            for window in chars.windows(order + 1) {
+                let prefix: String = window[..order].iter().collect();
+                let next = window[order];
+                *table.entry(prefix).or_default().entry(next).or_insert(0) += 1;
+            }
+        }
+
+        MarkovChain { table, order }
+    }
+
+    /// Samples the character following `prefix`, weighted by how often
+    /// it followed that prefix during training. Falls back to a uniform
+    /// pick over `a`-`z` when `prefix` was never observed.
+    fn sample_next(&self, prefix: &str, rng: &mut impl Rng) -> char {
+        match self.table.get(prefix) {
+            Some(follows) => {
+                let total: usize = follows.values().sum();
+                let mut pick = rng.gen_range(0..total);
+
+                for (&ch, &count) in follows {
+                    if pick < count {
+                        return ch;
+                    }
+                    pick -= count;
+                }
+
+                unreachable!("weighted pick must resolve to a character")
+            }
+            None => {
+                let i = rng.gen_range(0..26);
+                (b'a' + i as u8) as char
+            }
+        }
+    }
+
+    /// Estimates the average bits of entropy contributed by a single
+    /// sampled character, as the Shannon entropy of each prefix's
+    /// follow-up distribution averaged across prefixes, weighted by
+    /// how often each prefix was observed during training.
+    pub fn average_bits_per_char(&self) -> f64 {
+        let mut weighted_sum = 0.0;
+        let mut total_weight = 0.0;
+
+        for follows in self.table.values() {
+            let total: usize = follows.values().sum();
+            let total = total as f64;
+
+            let entropy: f64 = follows
+                .values()
+                .map(|&count| {
+                    let p = count as f64 / total;
+                    -p * p.log2()
+                })
+                .sum();
+
+            weighted_sum += entropy * total;
+            total_weight += total;
+        }
+
+        if total_weight == 0.0 {
+            0.0
+        } else {
+            weighted_sum / total_weight
+        }
+    }
+}
+
+/// Estimates the bits of entropy of a password produced by
+/// `produce_password(length, alphabets)`, computed as
+/// `length * log2(distinct_symbols)`, where `distinct_symbols` counts
+/// each symbol of the merged alphabets once (the generator itself draws
+/// from the merged alphabets without removing duplicates).
+pub fn password_entropy_bits(length: usize, alphabets: &Vec<Alphabet>) -> f64 {
+    let mut symbols: Vec<char> = Vec::new();
+    for abc in alphabets {
+        symbols.extend(abc.get());
+    }
+    symbols.sort_unstable();
+    symbols.dedup();
+
+    (length as f64) * (symbols.len() as f64).log2()
+}
+
+/// Estimates the bits of entropy of a passphrase produced by
+/// `produce_passphrase`, computed as `words * log2(wordlist_len)`.
+pub fn passphrase_entropy_bits(words: usize, wordlist_len: usize) -> f64 {
+    (words as f64) * (wordlist_len as f64).log2()
+}
+
+/// Qualitatively rates a number of bits of entropy as `"weak"` (<40),
+/// `"fair"` (<70), `"strong"` (<128) or `"excellent"` (>=128).
+pub fn entropy_rating(bits: f64) -> &'static str {
+    if bits < 40.0 {
+        "weak"
+    } else if bits < 70.0 {
+        "fair"
+    } else if bits < 128.0 {
+        "strong"
+    } else {
+        "excellent"
+    }
+}
+
+/// Produces a pronounceable password by sampling from a pre-trained
+/// Markov `chain` (see [`MarkovChain::train`]) until `length` characters
+/// have been generated. This trades some entropy for typability
+/// compared to the uniform `produce_password`.
+///
+/// Training a chain walks the whole corpus, so callers generating many
+/// passwords should train `chain` once and pass it to every call here
+/// instead of retraining per password.
+///
+/// Returns [`PwdError::ZeroLength`] if `length` is zero.
+pub fn produce_pronounceable(length: usize, chain: &MarkovChain) -> Result<String, PwdError> {
+    if length == 0 {
+        return Err(PwdError::ZeroLength);
+    }
+
+    let order = chain.order;
+    let mut rng = rand::thread_rng();
+    let mut history: Vec<char> = std::iter::repeat_n(SENTINEL, order).collect();
+    let mut password = String::with_capacity(length);
+
+    while password.chars().count() < length {
+        let prefix: String = history[history.len() - order..].iter().collect();
+        let next = chain.sample_next(&prefix, &mut rng);
+        password.push(next);
+        history.push(next);
+    }
+
+    Ok(password)
+}
+
+/// Estimates the bits of entropy of a password produced by
+/// `produce_pronounceable(length, chain)`, as an empirical estimate
+/// derived from the trained Markov model rather than a closed-form
+/// formula: the average per-character Shannon entropy of the model's
+/// transition tables, scaled by `length`.
+pub fn pronounceable_entropy_bits(length: usize, chain: &MarkovChain) -> f64 {
+    (length as f64) * chain.average_bits_per_char()
 }
@@ -23,6 +23,8 @@
 // ---------------------------------------------------------------------
 
 pub mod core;
+pub mod corpus;
+pub mod wordlist;
 
 // ---------------------------------------------------------------------
 // Unit testing
@@ -32,7 +34,12 @@ pub mod core;
 mod test {
     /// Provides tests for the core-module
     mod core {
-        use crate::core::{Alphabet, produce_password};
+        use crate::core::{
+            Alphabet, MarkovChain, PwdError, produce_password, produce_passphrase, produce_pronounceable,
+            preset_alphabet, password_entropy_bits, passphrase_entropy_bits, pronounceable_entropy_bits,
+            entropy_rating,
+        };
+        use crate::wordlist::WORDLIST;
 
         #[test]
         fn alphabet_len() {
@@ -66,32 +73,140 @@ mod test {
         }
 
         #[test]
-        #[should_panic]
-        fn produce_password_panic_on_false_length() {
+        fn produce_password_err_on_length_too_short() {
             let alphabets = vec![
                 Alphabet::new("ABCD"),
                 Alphabet::new("12"),
             ];
 
-            let _p = produce_password(1, &alphabets);
+            assert_eq!(Err(PwdError::LengthTooShort), produce_password(1, &alphabets));
         }
 
         #[test]
-        #[should_panic]
-        fn produce_password_panic_on_zero_length() {
+        fn produce_password_err_on_zero_length() {
             let alphabets = vec![
                 Alphabet::new("ABCD"),
                 Alphabet::new("12"),
             ];
 
-            let _p = produce_password(0, &alphabets);
+            assert_eq!(Err(PwdError::ZeroLength), produce_password(0, &alphabets));
         }
 
         #[test]
-        #[should_panic]
-        fn produce_password_panic_on_empty_alphabets() {
+        fn produce_password_err_on_empty_alphabets() {
             let alphabets: Vec<Alphabet> = Vec::new();
-            let _p = produce_password(8, &alphabets);
+            assert_eq!(Err(PwdError::NoAlphabets), produce_password(8, &alphabets));
+        }
+
+        #[test]
+        fn produce_passphrase_word_count_and_separator() {
+            let phrase = produce_passphrase(4, "-", WORDLIST).unwrap();
+            assert_eq!(4, phrase.split('-').count());
+        }
+
+        #[test]
+        fn produce_passphrase_words_are_from_wordlist() {
+            let phrase = produce_passphrase(3, " ", WORDLIST).unwrap();
+            for word in phrase.split(' ') {
+                assert!(WORDLIST.contains(&word));
+            }
+        }
+
+        #[test]
+        fn produce_passphrase_err_on_zero_words() {
+            assert_eq!(Err(PwdError::ZeroLength), produce_passphrase(0, "-", WORDLIST));
+        }
+
+        #[test]
+        fn produce_passphrase_err_on_empty_wordlist() {
+            assert_eq!(Err(PwdError::NoAlphabets), produce_passphrase(4, "-", &[]));
+        }
+
+        #[test]
+        fn preset_lowercase() {
+            assert_eq!(26, Alphabet::lowercase().len());
+        }
+
+        #[test]
+        fn preset_uppercase() {
+            assert_eq!(26, Alphabet::uppercase().len());
+        }
+
+        #[test]
+        fn preset_digits() {
+            assert_eq!(10, Alphabet::digits().len());
+        }
+
+        #[test]
+        fn preset_non_confusable_excludes_ambiguous_glyphs() {
+            let abc = Alphabet::non_confusable();
+            for glyph in ['0', 'O', '1', 'l', 'I'] {
+                assert!(!abc.get().contains(&glyph));
+            }
+        }
+
+        #[test]
+        fn preset_alphabet_resolves_known_names() {
+            assert!(preset_alphabet("lower").is_some());
+            assert!(preset_alphabet("nonconf").is_some());
+        }
+
+        #[test]
+        fn preset_alphabet_unknown_name_is_none() {
+            assert!(preset_alphabet("bogus").is_none());
+        }
+
+        #[test]
+        fn produce_pronounceable_respects_length() {
+            let chain = MarkovChain::train(2);
+            let password = produce_pronounceable(12, &chain).unwrap();
+            assert_eq!(12, password.chars().count());
+        }
+
+        #[test]
+        fn produce_pronounceable_is_lowercase_ascii() {
+            let chain = MarkovChain::train(2);
+            let password = produce_pronounceable(20, &chain).unwrap();
+            assert!(password.chars().all(|c| c.is_ascii_lowercase()));
+        }
+
+        #[test]
+        fn produce_pronounceable_err_on_zero_length() {
+            let chain = MarkovChain::train(2);
+            assert_eq!(Err(PwdError::ZeroLength), produce_pronounceable(0, &chain));
+        }
+
+        #[test]
+        fn produce_pronounceable_zero_order_does_not_panic() {
+            let chain = MarkovChain::train(0);
+            assert!(produce_pronounceable(8, &chain).is_ok());
+        }
+
+        #[test]
+        fn password_entropy_bits_uses_distinct_symbol_count() {
+            let alphabets = vec![Alphabet::new("01")];
+            assert_eq!(8.0, password_entropy_bits(8, &alphabets));
+        }
+
+        #[test]
+        fn passphrase_entropy_bits_formula() {
+            let bits = passphrase_entropy_bits(4, 16);
+            assert_eq!(16.0, bits);
+        }
+
+        #[test]
+        fn pronounceable_entropy_bits_is_positive() {
+            let chain = MarkovChain::train(2);
+            let bits = pronounceable_entropy_bits(10, &chain);
+            assert!(bits > 0.0);
+        }
+
+        #[test]
+        fn entropy_rating_boundaries() {
+            assert_eq!("weak", entropy_rating(30.0));
+            assert_eq!("fair", entropy_rating(50.0));
+            assert_eq!("strong", entropy_rating(100.0));
+            assert_eq!("excellent", entropy_rating(140.0));
         }
     }
 }
\ No newline at end of file
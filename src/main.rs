@@ -30,11 +30,113 @@ mod config {
     pub const APP_AUTHOR: &str = "Heiko Möllerke";
 }
 
+/// Provides the structured output formats (`plain`, `json`, `csv`) the
+/// cli can emit bulk-generated passwords in.
+mod output {
+    use pwdgen::core::entropy_rating;
+
+    /// A single generated password together with the metadata the
+    /// structured formats expose about it.
+    pub struct Entry {
+        pub password: String,
+        pub length: usize,
+        pub entropy_bits: f64,
+    }
+
+    /// Renders `entries` to stdout in the given `format` (`plain`,
+    /// `json` or `csv`); unknown format names fall back to `plain`.
+    /// `show_entropy` only affects `plain` output, where entropy is
+    /// opt-in; `json` and `csv` always carry the `entropy_bits` field.
+    pub fn emit(entries: &[Entry], format: &str, show_entropy: bool) {
+        match format {
+            "json" => emit_json(entries),
+            "csv" => emit_csv(entries),
+            _ => emit_plain(entries, show_entropy),
+        }
+    }
+
+    fn emit_plain(entries: &[Entry], show_entropy: bool) {
+        for entry in entries {
+            if show_entropy {
+                let rating = entropy_rating(entry.entropy_bits);
+                println!("{} ({:.1} bits, {})", entry.password, entry.entropy_bits, rating);
+            } else {
+                println!("{}", entry.password);
+            }
+        }
+    }
+
+    fn emit_json(entries: &[Entry]) {
+        let items: Vec<String> = entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{{\"password\": \"{}\", \"length\": {}, \"entropy_bits\": {:.2}}}",
+                    escape_json(&entry.password), entry.length, entry.entropy_bits
+                )
+            })
+            .collect();
+
+        println!("[{}]", items.join(", "));
+    }
+
+    fn emit_csv(entries: &[Entry]) {
+        println!("password,length,entropy_bits");
+
+        for entry in entries {
+            println!(
+                "{},{},{:.2}",
+                escape_csv(&entry.password), entry.length, entry.entropy_bits
+            );
+        }
+    }
+
+    /// Escapes `value` so it can be embedded in a JSON string: backslash
+    /// and quote are backslash-escaped, the common control characters
+    /// get their short-form escapes (`\n`, `\t`, `\r`, `\b`, `\f`)
+    /// and any other control character (U+0000-U+001F) is emitted as a
+    /// `\u00XX` escape.
+    fn escape_json(value: &str) -> String {
+        let mut escaped = String::with_capacity(value.len());
+
+        for c in value.chars() {
+            match c {
+                '\\' => escaped.push_str("\\\\"),
+                '"' => escaped.push_str("\\\""),
+                '\n' => escaped.push_str("\\n"),
+                '\t' => escaped.push_str("\\t"),
+                '\r' => escaped.push_str("\\r"),
+                '\u{8}' => escaped.push_str("\\b"),
+                '\u{c}' => escaped.push_str("\\f"),
+                c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+                c => escaped.push(c),
+            }
+        }
+
+        escaped
+    }
+
+    /// Quotes `value` per RFC 4180 if it contains a comma, quote or
+    /// newline, doubling any embedded quotes.
+    fn escape_csv(value: &str) -> String {
+        if value.contains(',') || value.contains('"') || value.contains('\n') {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+}
+
 /// Provides the functionalities of the application's
 /// command-line-interface (cli).
 mod cli {
-    use pwdgen::core::{Alphabet, produce_password};
+    use pwdgen::core::{
+        Alphabet, MarkovChain, PwdError, produce_password, produce_passphrase, produce_pronounceable,
+        preset_alphabet, password_entropy_bits, passphrase_entropy_bits, pronounceable_entropy_bits,
+    };
+    use pwdgen::wordlist::WORDLIST;
     use crate::config::*;
+    use crate::output::{self, Entry};
     use clap::{App, Arg};
 
     /// Run's the application's command-line-interface (aka the app).
@@ -50,7 +152,14 @@ mod cli {
                 .long("abc")
                 .multiple(true)
                 .takes_value(true)
-                .required(true))
+                .required_unless_one(&["DICEWARE", "PRESET", "PRONOUNCEABLE"]))
+            .arg(Arg::with_name("PRESET")
+                .short("p")
+                .long("preset")
+                .help("Comma-separated preset alphabets: lower, upper, digits, symbols, nonconf")
+                .multiple(true)
+                .use_delimiter(true)
+                .takes_value(true))
             .arg(Arg::with_name("LENGTH")
                 .short("l")
                 .long("length")
@@ -59,27 +168,136 @@ mod cli {
                 .short("n")
                 .long("number")
                 .takes_value(true))
+            .arg(Arg::with_name("DICEWARE")
+                .short("d")
+                .long("diceware")
+                .help("Generate a Diceware-style passphrase instead of a password")
+                .takes_value(false))
+            .arg(Arg::with_name("WORDS")
+                .short("w")
+                .long("words")
+                .help("Number of words in the passphrase (used with --diceware)")
+                .takes_value(true))
+            .arg(Arg::with_name("SEPARATOR")
+                .long("separator")
+                .help("Separator placed between passphrase words (used with --diceware)")
+                .takes_value(true))
+            .arg(Arg::with_name("PRONOUNCEABLE")
+                .short("r")
+                .long("pronounceable")
+                .help("Generate a pronounceable password via a character-level Markov chain")
+                .takes_value(false))
+            .arg(Arg::with_name("ORDER")
+                .long("order")
+                .help("Markov chain order, i.e. how many preceding characters predict the next one (used with --pronounceable)")
+                .takes_value(true))
+            .arg(Arg::with_name("ENTROPY")
+                .short("e")
+                .long("entropy")
+                .help("Print the estimated bits of entropy and a strength rating alongside each password (plain format only; json and csv always include entropy_bits)")
+                .takes_value(false))
+            .arg(Arg::with_name("FORMAT")
+                .short("f")
+                .long("format")
+                .help("Output format for bulk generation: plain, json or csv")
+                .possible_values(&["plain", "json", "csv"])
+                .default_value("plain")
+                .takes_value(true))
             .get_matches();
 
-        let length = matches.value_of("LENGTH").unwrap_or("8");
+        let show_entropy = matches.is_present("ENTROPY");
+        let format = matches.value_of("FORMAT").unwrap_or("plain");
+
         let number = matches.value_of("NUMBER").unwrap_or("1");
-        let alphabets: Vec<&str> = matches
-            .values_of("ALPHABET")
-            .unwrap()
-            .collect();
+        let number: usize = number.trim().parse().unwrap_or(1);
+
+        if matches.is_present("DICEWARE") {
+            let words = matches.value_of("WORDS").unwrap_or("6");
+            let words: usize = words.trim().parse().unwrap_or(6);
+            let separator = matches.value_of("SEPARATOR").unwrap_or("-");
+            let bits = passphrase_entropy_bits(words, WORDLIST.len());
+
+            let entries: Result<Vec<Entry>, PwdError> = (0..number)
+                .map(|_| {
+                    produce_passphrase(words, separator, WORDLIST)
+                        .map(|phrase| Entry { length: phrase.chars().count(), password: phrase, entropy_bits: bits })
+                })
+                .collect();
+
+            match entries {
+                Ok(entries) => output::emit(&entries, format, show_entropy),
+                Err(e) => {
+                    eprintln!("Could not generate passphrase: {}", e);
+                    std::process::exit(1);
+                }
+            }
+
+            return;
+        }
+
+        let length = matches.value_of("LENGTH").unwrap_or("8");
+
+        if matches.is_present("PRONOUNCEABLE") {
+            let length: usize = length.trim().parse().unwrap_or(8);
+            let order = matches.value_of("ORDER").unwrap_or("2");
+            let order: usize = order.trim().parse().unwrap_or(2);
+
+            // Train the Markov chain once and reuse it for every
+            // password in this bulk run, rather than retraining it
+            // per password.
+            let chain = MarkovChain::train(order);
+            let bits = pronounceable_entropy_bits(length, &chain);
+
+            let entries: Result<Vec<Entry>, PwdError> = (0..number)
+                .map(|_| {
+                    produce_pronounceable(length, &chain)
+                        .map(|password| Entry { password, length, entropy_bits: bits })
+                })
+                .collect();
+
+            match entries {
+                Ok(entries) => output::emit(&entries, format, show_entropy),
+                Err(e) => {
+                    eprintln!("Could not generate password: {}", e);
+                    std::process::exit(1);
+                }
+            }
+
+            return;
+        }
 
         // After successful parsing and extraction parse the slice
         // parameters into numbers and required objects (shadows):
         let length: usize = length.trim().parse().unwrap_or(8);
-        let number: usize = number.trim().parse().unwrap_or(1);
-        let alphabets: Vec<Alphabet> = alphabets
-            .iter()
-            .map(|&s| Alphabet::new(s))
-            .collect();
+        let mut alphabets: Vec<Alphabet> = matches
+            .values_of("ALPHABET")
+            .map(|vals| vals.map(Alphabet::new).collect())
+            .unwrap_or_else(Vec::new);
+
+        if let Some(presets) = matches.values_of("PRESET") {
+            for name in presets {
+                match preset_alphabet(name) {
+                    Some(abc) => alphabets.push(abc),
+                    None => eprintln!("Ignoring unknown preset alphabet: {}", name),
+                }
+            }
+        }
 
         // Create password-factory and output passwords.
-        for _ in 0..number {
-            println!("{}", produce_password(length, &alphabets));
+        let bits = password_entropy_bits(length, &alphabets);
+        let entries: Result<Vec<Entry>, PwdError> = (0..number)
+            .map(|_| {
+                produce_password(length, &alphabets)
+                    .map(|password| Entry { password, length, entropy_bits: bits })
+            })
+            .collect();
+
+        match entries {
+            Ok(entries) => output::emit(&entries, format, show_entropy),
+            Err(e) => {
+                eprintln!("Could not generate password: {}", e);
+                std::process::exit(1);
+            }
         }
     }
 }